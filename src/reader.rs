@@ -1,5 +1,6 @@
 
-use std::io::{Result, Read, Seek, ErrorKind, Error, SeekFrom};
+use std::collections::VecDeque;
+use std::io::{Result, Read, BufRead, Seek, ErrorKind, Error, SeekFrom};
 
 pub struct BufStreamReader<R> where R: Read {
     reader: R,
@@ -7,12 +8,19 @@ pub struct BufStreamReader<R> where R: Read {
     bytes_in_buffer: usize,
     buffer: Vec<u8>,
     current_in_buffer: u64,
+    history: Option<VecDeque<u8>>,
+    history_bytes: usize,
+    replay_pos: Option<u64>,
+    back_chunk_start: u64,
+    back_len: usize,
+    back_cursor: Option<u64>,
+    progress: Option<Box<dyn FnMut(u64)>>,
 }
 
 impl<R> BufStreamReader<R> where R: Read {
     /// Creates a new BufStreamReader with a specified `buffer_size`.
     /// This newly created object wraps another object which is [Read](std::io::Read).
-    /// 
+    ///
     ///  - `buffer_size` - Size of the read buffer. [BufStreamReader] always tries to read `buffer_size` bytes from ` reader, but it is not guaranteed that the buffer actually holds that number of bytes (e.g. at the end of the stream)
     ///  - `reader` - Reader which has to be wrapped
     pub fn new(reader: R, buffer_size: usize) -> Self {
@@ -22,10 +30,43 @@ impl<R> BufStreamReader<R> where R: Read {
             buffer,
             bytes_in_buffer: 0,
             offset: 0,
-            current_in_buffer: 0
+            current_in_buffer: 0,
+            history: None,
+            history_bytes: 0,
+            replay_pos: None,
+            back_chunk_start: 0,
+            back_len: 0,
+            back_cursor: None,
+            progress: None,
         }
     }
 
+    /// Registers a callback that is invoked every time more data is drawn
+    /// from the wrapped reader, with the total number of bytes drawn so far.
+    ///
+    /// This is useful for driving a progress bar while parsing a large
+    /// stream, without having to wrap the inner reader separately just to
+    /// count bytes.
+    pub fn with_progress<F>(mut self, callback: F) -> Self where F: FnMut(u64) + 'static {
+        self.progress = Some(Box::new(callback));
+        self
+    }
+
+    /// Creates a new BufStreamReader which, in addition to the usual
+    /// `buffer_size` bytes lookahead buffer, retains up to `history_bytes`
+    /// of the most recently consumed bytes in a lookback window.
+    ///
+    /// This allows backward seeks up to `history_bytes` behind the current
+    /// position even if the wrapped `reader` does not implement
+    /// [Seek](std::io::Seek) itself (e.g. pipes, sockets, decompressors),
+    /// at the cost of keeping that many bytes around in memory.
+    pub fn with_history(reader: R, buffer_size: usize, history_bytes: usize) -> Self {
+        let mut me = Self::new(reader, buffer_size);
+        me.history = Some(VecDeque::with_capacity(history_bytes));
+        me.history_bytes = history_bytes;
+        me
+    }
+
     /// Returns the offset of the current buffer in the wrapped stream.
     pub fn offset(&self) -> u64 {
         self.offset
@@ -37,17 +78,82 @@ impl<R> BufStreamReader<R> where R: Read {
 
     fn read_next_buffer(&mut self) -> Result<()> {
 
+        // snapshot the buffer we are about to discard *before* it gets
+        // overwritten below, but don't commit it to history until we know
+        // we actually got a new buffer - otherwise a repeated call at EOF
+        // (e.g. a second `fill_buf()` after the stream ended) would fold the
+        // very same bytes into history again and again, evicting genuinely
+        // older, still-retained history
+        let old_bytes_in_buffer = self.bytes_in_buffer;
+        let old_buffer = self.history.is_some().then(|| self.buffer[..old_bytes_in_buffer].to_vec());
+
         let bytes = self.reader.read(&mut self.buffer[..])?;
         if bytes == 0 {
             return Err(Error::new(ErrorKind::UnexpectedEof, "read 0 bytes"));
         }
 
-        self.offset += self.bytes_in_buffer as u64;
+        if let Some(old_buffer) = old_buffer {
+            self.push_history(&old_buffer);
+        }
+
+        self.offset += old_bytes_in_buffer as u64;
         self.bytes_in_buffer = bytes;
         self.current_in_buffer = 0;
+
+        if let Some(progress) = &mut self.progress {
+            progress(self.offset + self.bytes_in_buffer as u64);
+        }
+
         Ok(())
     }
 
+    /// Appends `bytes` (the data that is about to leave the live buffer) to
+    /// the retained history, dropping the oldest bytes beyond
+    /// `history_bytes`. A no-op if history tracking is not enabled.
+    fn push_history(&mut self, bytes: &[u8]) {
+        if let Some(history) = &mut self.history {
+            if bytes.len() >= self.history_bytes {
+                history.clear();
+                history.extend(&bytes[bytes.len() - self.history_bytes..]);
+            } else {
+                history.extend(bytes.iter().copied());
+                while history.len() > self.history_bytes {
+                    history.pop_front();
+                }
+            }
+        }
+    }
+
+    /// Copies up to `dst.len()` bytes out of the retained history, starting
+    /// at `self.replay_pos`, and advances `replay_pos` accordingly. Once it
+    /// catches up with the live buffer at `self.offset`, clears `replay_pos`
+    /// and rewinds `current_in_buffer` so the following read resumes at the
+    /// start of the still-intact live buffer instead of wherever it was
+    /// left off before the backward seek.
+    ///
+    /// Must only be called while `self.replay_pos` is `Some`.
+    fn read_from_history(&mut self, dst: &mut [u8]) -> usize {
+        let history = self.history.as_ref().expect("read_from_history() called without history");
+        let pos = self.replay_pos.expect("read_from_history() called without a replay position");
+        let history_start = self.offset - history.len() as u64;
+        let idx = (pos - history_start) as usize;
+
+        let avail = history.len() - idx;
+        let n = std::cmp::min(avail, dst.len());
+        for i in 0..n {
+            dst[i] = history[idx + i];
+        }
+
+        let new_pos = pos + n as u64;
+        if new_pos == self.offset {
+            self.replay_pos = None;
+            self.current_in_buffer = 0;
+        } else {
+            self.replay_pos = Some(new_pos);
+        }
+        n
+    }
+
     /// jump a certain number of blocks forward
     fn seek_until_position(&mut self, mut position_in_buffer: u64) -> Result<u64> {
         while position_in_buffer >= self.bytes_in_buffer as u64 {
@@ -66,11 +172,64 @@ impl<R> Read for BufStreamReader<R> where R: Read {
     fn read(&mut self, dst: &mut [u8]) -> Result<usize> {
         let mut bytes_read = 0;
         loop {
+            // a backward seek into the retained history is being replayed;
+            // serve bytes from there until we catch up with the live buffer
+            if self.replay_pos.is_some() {
+                let n = self.read_from_history(&mut dst[bytes_read..]);
+                bytes_read += n;
+
+                if bytes_read == dst.len() {
+                    return Ok(bytes_read);
+                }
+
+                continue;
+            }
+
             let can_read = self.bytes_in_buffer - self.current_in_buffer as usize;
 
             // the current buffer contains no more data to return, we must obtain
             // no data from the wrapped reader
             if can_read == 0 {
+                // `dst` is at least as big as our internal buffer, so filling
+                // the internal buffer just to copy it straight back out would
+                // be a wasted round trip; read directly into `dst` instead
+                if dst.len() - bytes_read >= self.buffer.len() {
+                    let bytes = self.reader.read(&mut dst[bytes_read..])?;
+                    if bytes == 0 {
+                        if bytes_read > 0 {
+                            return Ok(bytes_read);
+                        } else {
+                            return Err(Error::new(ErrorKind::UnexpectedEof, "read 0 bytes"));
+                        }
+                    }
+
+                    // retire the exhausted live buffer first, just like
+                    // `read_next_buffer()` does, so that `offset` and the
+                    // history keep accounting for its bytes
+                    let old_bytes_in_buffer = self.bytes_in_buffer;
+                    if self.history.is_some() {
+                        let old_buffer = self.buffer[..old_bytes_in_buffer].to_vec();
+                        self.push_history(&old_buffer);
+                    }
+                    self.push_history(&dst[bytes_read..bytes_read + bytes]);
+
+                    self.offset += (old_bytes_in_buffer + bytes) as u64;
+                    self.bytes_in_buffer = 0;
+                    self.current_in_buffer = 0;
+                    bytes_read += bytes;
+
+                    if let Some(progress) = &mut self.progress {
+                        progress(self.offset);
+                    }
+
+                    if bytes_read == dst.len() {
+                        return Ok(bytes_read);
+                    }
+
+                    // we need to read more bytes
+                    continue;
+                }
+
                 if let Err(why) = self.read_next_buffer() {
 
                     // the wrapped reader encountered an EOF, so we are stuck
@@ -115,15 +274,59 @@ impl<R> Read for BufStreamReader<R> where R: Read {
     }
 }
 
+impl<R> BufRead for BufStreamReader<R> where R: Read {
+    fn fill_buf(&mut self) -> Result<&[u8]> {
+        // a backward seek into the retained history is being replayed;
+        // serve bytes from there, just like `Read::read` does
+        if let Some(pos) = self.replay_pos {
+            let history = self.history.as_mut().expect("replay_pos set without history");
+            let idx = (pos - (self.offset - history.len() as u64)) as usize;
+            return Ok(&history.make_contiguous()[idx..]);
+        }
+
+        if self.current_in_buffer == self.bytes_in_buffer as u64 {
+            if let Err(why) = self.read_next_buffer() {
+                // at EOF there simply is nothing left to return; unlike
+                // `read()` we have no bytes_read to fall back on, so we
+                // report an empty slice instead of an error
+                if why.kind() != ErrorKind::UnexpectedEof {
+                    return Err(why);
+                }
+            }
+        }
+
+        Ok(&self.buffer[self.current_in_buffer as usize..self.bytes_in_buffer])
+    }
+
+    fn consume(&mut self, amt: usize) {
+        if let Some(pos) = self.replay_pos {
+            let new_pos = pos + amt as u64;
+            if new_pos == self.offset {
+                self.replay_pos = None;
+                self.current_in_buffer = 0;
+            } else {
+                self.replay_pos = Some(new_pos);
+            }
+            return;
+        }
+
+        self.current_in_buffer += amt as u64;
+        assert!(self.current_in_buffer <= self.bytes_in_buffer as u64);
+    }
+}
+
 impl<R> Seek for BufStreamReader<R> where R: Read {
     fn seek(&mut self, seek_from: SeekFrom) -> Result<u64> {
         match seek_from {
             SeekFrom::Start(pos) => {
-                // don't seek befor the end of the current buffer
+                // don't seek befor the start of the current buffer, unless it
+                // is still within the retained history
                 if pos < self.offset {
-                    return Err(Error::new(ErrorKind::InvalidData, "cannot seek before current buffer"));
+                    return self.seek_into_history(pos);
                 }
 
+                self.replay_pos = None;
+
                 // We can seek behind the end of the current buffer,
                 // but this requires discarding the current buffer
                 // and reloading a new buffer.
@@ -131,13 +334,18 @@ impl<R> Seek for BufStreamReader<R> where R: Read {
             }
 
             SeekFrom::Current(pos) => {
-                if pos < 0 {
-                    let pos = -pos as u64;
-                    if pos > self.current_in_buffer {
-                        return Err(Error::new(ErrorKind::InvalidData, "cannot seek before current buffer"))
-                    }
+                let current = self.replay_pos.unwrap_or(self.offset + self.current_in_buffer);
+                if pos < 0 && (-pos) as u64 > current {
+                    return Err(Error::new(ErrorKind::InvalidData, "cannot seek before the start of the stream"));
                 }
-                self.seek_until_position((pos + (self.current_in_buffer as i64)) as u64)
+
+                let target = (current as i64 + pos) as u64;
+                if target < self.offset {
+                    return self.seek_into_history(target);
+                }
+
+                self.replay_pos = None;
+                self.seek_until_position(target - self.offset)
             }
 
             // We don't know where the end of a stream is, so this cannot be implemented
@@ -146,4 +354,365 @@ impl<R> Seek for BufStreamReader<R> where R: Read {
             }
         }
     }
+}
+
+impl<R> BufStreamReader<R> where R: Read {
+    /// Attempts to satisfy a backward seek to the absolute position `pos`
+    /// out of the retained history window, see [`Self::with_history`].
+    ///
+    /// Errors if `pos` predates the retained window (or there is none).
+    fn seek_into_history(&mut self, pos: u64) -> Result<u64> {
+        if let Some(history) = &self.history {
+            let history_start = self.offset - history.len() as u64;
+            if pos >= history_start {
+                self.replay_pos = Some(pos);
+                return Ok(pos);
+            }
+        }
+
+        Err(Error::new(ErrorKind::InvalidData, "cannot seek before the retained history"))
+    }
+}
+
+impl<R> BufStreamReader<R> where R: Read + Seek {
+    /// Resets the internal buffer and records `new_offset` as the position
+    /// of the (not yet filled) next buffer, so that the following `read()`
+    /// pulls fresh data from the wrapped reader's new position.
+    ///
+    /// Also drops any retained history: it was built from the bytes leading
+    /// up to the *old* position, so it no longer describes the bytes
+    /// preceding `new_offset`. Backward seeks via `Seek::seek` are disabled
+    /// until enough history accumulates again from `new_offset` onward.
+    fn discard_buffer(&mut self, new_offset: u64) {
+        self.bytes_in_buffer = 0;
+        self.current_in_buffer = 0;
+        self.offset = new_offset;
+        self.replay_pos = None;
+        self.back_cursor = None;
+        self.back_chunk_start = 0;
+        self.back_len = 0;
+
+        if let Some(history) = &mut self.history {
+            history.clear();
+        }
+    }
+
+    /// Seeks to an arbitrary position in the wrapped stream, including
+    /// backward seeks that go further back than the current buffer and the
+    /// retained history (see [`Self::with_history`]) and
+    /// [`SeekFrom::End`](std::io::SeekFrom::End), neither of which are
+    /// supported by the [`Seek`](std::io::Seek) implementation available
+    /// for any `R: Read`.
+    ///
+    /// This is only available if the wrapped reader `R` also implements
+    /// [`Seek`](std::io::Seek): the current buffer is simply discarded and
+    /// the seek is delegated to the wrapped reader, after which the next
+    /// `read()` refills the buffer from the true position. Callers who
+    /// don't happen to have a seekable source can keep using
+    /// [`Seek::seek`](std::io::Seek::seek), whose backward seeks are limited
+    /// to the current buffer and the retained history, instead.
+    pub fn seek_inner(&mut self, seek_from: SeekFrom) -> Result<u64> {
+        match seek_from {
+            SeekFrom::Start(pos) => {
+                self.reader.seek(SeekFrom::Start(pos))?;
+                self.discard_buffer(pos);
+                Ok(pos)
+            }
+
+            SeekFrom::Current(pos) => {
+                let abs = self.replay_pos.unwrap_or(self.offset + self.current_in_buffer);
+                if pos < 0 && (-pos) as u64 > abs {
+                    return Err(Error::new(ErrorKind::InvalidData, "cannot seek before the start of the stream"));
+                }
+
+                let new_pos = self.reader.seek(SeekFrom::Start((abs as i64 + pos) as u64))?;
+                self.discard_buffer(new_pos);
+                Ok(new_pos)
+            }
+
+            SeekFrom::End(pos) => {
+                let new_pos = self.reader.seek(SeekFrom::End(pos))?;
+                self.discard_buffer(new_pos);
+                Ok(new_pos)
+            }
+        }
+    }
+
+    /// Reads `dst.len()` bytes immediately preceding the current position,
+    /// in forward order, and moves the logical cursor backward by that
+    /// many bytes.
+    ///
+    /// This is useful for trailer/footer-based formats (indexes written at
+    /// the end of a stream, last-record-first logs) that are best parsed
+    /// from the tail backward. It maintains its own cursor, independent of
+    /// the one used by `read()`/`seek()`; mix the two only after issuing a
+    /// matching `seek_inner()` to re-synchronize the forward cursor.
+    ///
+    /// Returns fewer bytes than requested once the start of the stream is
+    /// reached, and `Ok(0)` once there is nothing left before it.
+    pub fn read_back(&mut self, dst: &mut [u8]) -> Result<usize> {
+        let forward_pos = self.replay_pos.unwrap_or(self.offset + self.current_in_buffer);
+        let mut pos = self.back_cursor.unwrap_or(forward_pos);
+        let want = dst.len();
+        let mut bytes_read = 0;
+
+        while bytes_read < want && pos > 0 {
+            // the back-buffer doesn't cover `pos` (yet); refill it with the
+            // chunk of up to `buffer.len()` bytes that ends at `pos`
+            if pos <= self.back_chunk_start || pos > self.back_chunk_start + self.back_len as u64 {
+                let chunk_start = pos.saturating_sub(self.buffer.len() as u64);
+                self.reader.seek(SeekFrom::Start(chunk_start))?;
+                self.back_len = self.reader.read(&mut self.buffer[..])?;
+                self.back_chunk_start = chunk_start;
+            }
+
+            let chunk_end = self.back_chunk_start + self.back_len as u64;
+            let avail = (std::cmp::min(pos, chunk_end) - self.back_chunk_start) as usize;
+            let n = std::cmp::min(avail, want - bytes_read);
+
+            let src_end = avail;
+            let src_begin = src_end - n;
+            let dst_end = want - bytes_read;
+            let dst_begin = dst_end - n;
+            dst[dst_begin..dst_end].copy_from_slice(&self.buffer[src_begin..src_end]);
+
+            bytes_read += n;
+            pos -= n as u64;
+        }
+
+        self.back_cursor = Some(pos);
+
+        // `read_back` stages its chunks in the same `buffer` the forward
+        // `read()` path serves from, so it just clobbered whatever that
+        // path had cached. Invalidate the forward buffer and reposition the
+        // inner reader at the (unchanged) forward cursor, so the next
+        // forward read refills from the correct spot instead of serving
+        // bytes `read_back` overwrote. The retained history ends at the old
+        // `offset`, so it no longer describes the bytes preceding the new
+        // one and is dropped, as in `discard_buffer()`.
+        self.reader.seek(SeekFrom::Start(forward_pos))?;
+        self.bytes_in_buffer = 0;
+        self.current_in_buffer = 0;
+        self.offset = forward_pos;
+        self.replay_pos = None;
+
+        if let Some(history) = &mut self.history {
+            history.clear();
+        }
+
+        Ok(bytes_read)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BufStreamReader;
+    use std::io::{BufRead, Cursor, Read, Seek, SeekFrom};
+
+    #[test]
+    fn history_survives_repeated_eof_fill_buf_calls() {
+        let data: Vec<u8> = (0..20).collect();
+        let mut reader = BufStreamReader::with_history(Cursor::new(data), 4, 8);
+
+        let mut byte = [0u8; 1];
+        for _ in 0..20 {
+            reader.read_exact(&mut byte).unwrap();
+        }
+
+        // the stream is exhausted; repeated fill_buf() calls at EOF must not
+        // fold the same stale buffer into history again and again
+        for _ in 0..3 {
+            assert_eq!(reader.fill_buf().unwrap(), &[][..]);
+        }
+
+        assert_eq!(reader.seek(SeekFrom::Start(10)).unwrap(), 10);
+        let mut out = [0u8; 6];
+        reader.read_exact(&mut out).unwrap();
+        assert_eq!(out, [10, 11, 12, 13, 14, 15]);
+    }
+
+    #[test]
+    fn large_read_bypass_still_feeds_history() {
+        let data: Vec<u8> = (0..20).collect();
+        let mut reader = BufStreamReader::with_history(Cursor::new(data), 4, 8);
+
+        // this read is large enough to take the buffer-bypass fast path
+        let mut out = [0u8; 20];
+        reader.read_exact(&mut out).unwrap();
+
+        assert_eq!(reader.seek(SeekFrom::Start(12)).unwrap(), 12);
+        let mut replayed = [0u8; 4];
+        reader.read_exact(&mut replayed).unwrap();
+        assert_eq!(replayed, [12, 13, 14, 15]);
+    }
+
+    #[test]
+    fn fill_buf_serves_replayed_history_then_resumes_live_buffer() {
+        let data: Vec<u8> = (0..20).collect();
+        let mut reader = BufStreamReader::with_history(Cursor::new(data), 4, 8);
+
+        let mut byte = [0u8; 1];
+        for _ in 0..20 {
+            reader.read_exact(&mut byte).unwrap();
+        }
+
+        assert_eq!(reader.seek(SeekFrom::Start(10)).unwrap(), 10);
+
+        let replayed = reader.fill_buf().unwrap().to_vec();
+        assert_eq!(replayed, vec![10, 11, 12, 13, 14, 15]);
+        reader.consume(replayed.len());
+
+        // replay is now exhausted; fill_buf() should hand back the still
+        // intact live buffer from its start, not an empty/stale slice
+        assert_eq!(reader.fill_buf().unwrap(), &[16, 17, 18, 19][..]);
+    }
+
+    #[test]
+    fn read_back_cursor_resets_on_seek_inner() {
+        let data: Vec<u8> = (0..20).collect();
+        let mut reader = BufStreamReader::new(Cursor::new(data), 4);
+
+        reader.seek_inner(SeekFrom::End(0)).unwrap();
+        let mut tail = [0u8; 5];
+        reader.read_back(&mut tail).unwrap();
+        assert_eq!(tail, [15, 16, 17, 18, 19]);
+
+        reader.seek_inner(SeekFrom::Start(8)).unwrap();
+        let mut before_eight = [0u8; 5];
+        reader.read_back(&mut before_eight).unwrap();
+        assert_eq!(before_eight, [3, 4, 5, 6, 7]);
+    }
+
+    #[test]
+    fn read_back_does_not_corrupt_forward_read() {
+        let data: Vec<u8> = (0..20).collect();
+        let mut reader = BufStreamReader::new(Cursor::new(data), 4);
+
+        let mut byte = [0u8; 1];
+        for _ in 0..10 {
+            reader.read_exact(&mut byte).unwrap();
+        }
+
+        let mut back = [0u8; 4];
+        reader.read_back(&mut back).unwrap();
+
+        // the forward cursor is still sitting right after byte 9; reading
+        // onward must not see the bytes `read_back` just staged in `buffer`
+        let mut forward = [0u8; 2];
+        reader.read_exact(&mut forward).unwrap();
+        assert_eq!(forward, [10, 11]);
+    }
+
+    #[test]
+    fn buf_read_fill_buf_and_consume_drain_the_stream() {
+        let data: Vec<u8> = (0..10).collect();
+        let mut reader = BufStreamReader::new(Cursor::new(data), 4);
+
+        let first = reader.fill_buf().unwrap().to_vec();
+        assert_eq!(first, vec![0, 1, 2, 3]);
+        reader.consume(2);
+
+        // a partial consume must be reflected on the next fill_buf() call
+        let second = reader.fill_buf().unwrap().to_vec();
+        assert_eq!(second, vec![2, 3]);
+        reader.consume(2);
+
+        let mut collected = Vec::new();
+        loop {
+            let chunk = reader.fill_buf().unwrap();
+            if chunk.is_empty() {
+                break;
+            }
+            collected.extend_from_slice(chunk);
+            let n = chunk.len();
+            reader.consume(n);
+        }
+        assert_eq!(collected, vec![4, 5, 6, 7, 8, 9]);
+    }
+
+    #[test]
+    fn seek_inner_supports_start_current_and_end() {
+        let data: Vec<u8> = (0..20).collect();
+        let mut reader = BufStreamReader::new(Cursor::new(data), 4);
+
+        assert_eq!(reader.seek_inner(SeekFrom::Start(5)).unwrap(), 5);
+        let mut buf = [0u8; 3];
+        reader.read_exact(&mut buf).unwrap();
+        assert_eq!(buf, [5, 6, 7]);
+
+        assert_eq!(reader.seek_inner(SeekFrom::Current(-2)).unwrap(), 6);
+        reader.read_exact(&mut buf).unwrap();
+        assert_eq!(buf, [6, 7, 8]);
+
+        assert_eq!(reader.seek_inner(SeekFrom::End(-3)).unwrap(), 17);
+        reader.read_exact(&mut buf).unwrap();
+        assert_eq!(buf, [17, 18, 19]);
+    }
+
+    #[test]
+    fn seek_inner_current_rejects_negative_overshoot() {
+        let data: Vec<u8> = (0..20).collect();
+        let mut reader = BufStreamReader::new(Cursor::new(data), 4);
+
+        reader.seek_inner(SeekFrom::Start(5)).unwrap();
+        assert!(reader.seek_inner(SeekFrom::Current(-10)).is_err());
+    }
+
+    #[test]
+    fn seek_inner_invalidates_history_without_panicking() {
+        let data: Vec<u8> = (0..20).collect();
+        let mut reader = BufStreamReader::with_history(Cursor::new(data), 4, 8);
+
+        let mut byte = [0u8; 1];
+        for _ in 0..10 {
+            reader.read_exact(&mut byte).unwrap();
+        }
+
+        reader.seek_inner(SeekFrom::Start(2)).unwrap();
+
+        // before the fix this underflowed `self.offset - history.len()` in
+        // `seek_into_history`, since history still held 8 bytes despite
+        // `offset` having just dropped to 2
+        assert!(reader.seek(SeekFrom::Start(1)).is_err());
+    }
+
+    #[test]
+    fn large_read_bypasses_internal_buffer() {
+        let data: Vec<u8> = (0..50).collect();
+        let mut reader = BufStreamReader::new(Cursor::new(data.clone()), 4);
+
+        // this read is at least as large as the internal buffer, so it
+        // should go straight into `bulk` instead of through `self.buffer`
+        let mut bulk = [0u8; 20];
+        reader.read_exact(&mut bulk).unwrap();
+        assert_eq!(&bulk[..], &data[0..20]);
+
+        // a small read right after a bypassed large read must continue
+        // exactly where the bulk read left off
+        let mut tail = [0u8; 3];
+        reader.read_exact(&mut tail).unwrap();
+        assert_eq!(tail, [20, 21, 22]);
+    }
+
+    #[test]
+    fn with_progress_reports_cumulative_bytes_drawn() {
+        use std::cell::RefCell;
+        use std::rc::Rc;
+
+        let data: Vec<u8> = (0..10).collect();
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        let seen_in_callback = Rc::clone(&seen);
+
+        let mut reader = BufStreamReader::new(Cursor::new(data), 4)
+            .with_progress(move |total| seen_in_callback.borrow_mut().push(total));
+
+        let mut byte = [0u8; 1];
+        for _ in 0..10 {
+            reader.read_exact(&mut byte).unwrap();
+        }
+
+        // one callback per refill from the wrapped reader: two full 4-byte
+        // buffers, then a final short 2-byte one at EOF
+        assert_eq!(*seen.borrow(), vec![4, 8, 10]);
+    }
 }
\ No newline at end of file